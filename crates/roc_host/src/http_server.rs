@@ -5,14 +5,40 @@ use hyper::header::{HeaderName, HeaderValue};
 use roc_std::{RocList, RocStr};
 use std::convert::Infallible;
 use std::env;
+use std::fs::File;
+use std::io::{self, BufReader, Write};
 use std::net::SocketAddr;
 use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::sync::OnceLock;
+use base64::Engine;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sha1::{Digest, Sha1};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
 use tokio::task::spawn_blocking;
+use tokio_rustls::TlsAcceptor;
 
 const DEFAULT_PORT: u16 = 8000;
 const HOST_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_HOST";
 const PORT_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_PORT";
+const TLS_CERT_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_TLS_CERT";
+const TLS_KEY_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_TLS_KEY";
+const TLS_ALPN_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_TLS_ALPN";
+
+/// The fixed GUID from RFC 6455 that is concatenated with the client key to
+/// derive the `Sec-WebSocket-Accept` value.
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B39";
+
+const MAX_BODY_BYTES_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_MAX_BODY_BYTES";
+const DEFAULT_MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+const COMPRESSION_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_COMPRESSION";
+const COMPRESSION_MIN_BYTES_ENV_NAME: &str = "ROC_BASIC_WEBSERVER_COMPRESSION_MIN_BYTES";
+const DEFAULT_COMPRESSION_MIN_BYTES: usize = 1024;
 
 static ROC_MODEL: OnceLock<roc::Model> = OnceLock::new();
 
@@ -41,7 +67,7 @@ fn call_roc<'a>(
     url: hyper::Uri,
     headers: impl Iterator<Item = (&'a HeaderName, &'a HeaderValue)>,
     body: Bytes,
-) -> hyper::Response<hyper::Body> {
+) -> roc_http::ResponseToHost {
     let headers: RocList<roc_http::Header> = headers
         .map(|(key, value)| roc_http::Header {
             name: key.as_str().into(),
@@ -77,27 +103,54 @@ fn call_roc<'a>(
         timeout_ms: 0,
     };
 
-    let roc_response = roc::call_roc_respond(
+    roc::call_roc_respond(
         roc_request,
         ROC_MODEL.get().expect("Model was initialized at startup"),
-    );
-
-    roc_response.into()
+    )
 }
 
 async fn handle_req(req: hyper::Request<hyper::Body>) -> hyper::Response<hyper::Body> {
+    if is_websocket_upgrade(req.headers()) {
+        return handle_websocket(req).await;
+    }
+
     let (parts, body) = req.into_parts();
+    let limit = max_body_bytes();
+    let accepts_gzip = accepts_gzip(&parts.headers);
 
-    #[allow(deprecated)]
-    match hyper::body::to_bytes(body).await {
+    // Reject obviously-too-large uploads from the advertised `Content-Length`
+    // before reading a single byte of the stream.
+    if let Some(len) = content_length(&parts.headers) {
+        if len > limit {
+            return payload_too_large();
+        }
+    }
+
+    match collect_body(body, limit).await {
         Ok(body) => {
-            spawn_blocking(move || call_roc(parts.method, parts.uri, parts.headers.iter(), body))
-                .then(|resp| async {
-                    resp.unwrap() // TODO don't unwrap here
-                })
-                .await
+            let method = parts.method.clone();
+            let headers = parts.headers.clone();
+            let roc_body = body.clone();
+
+            let directive = spawn_blocking(move || {
+                call_roc(parts.method, parts.uri, parts.headers.iter(), roc_body)
+            })
+            .then(|resp| async {
+                resp.unwrap() // TODO don't unwrap here
+            })
+            .await;
+
+            match directive {
+                roc_http::ResponseToHost::Respond(response) => {
+                    maybe_compress(response.into(), accepts_gzip).await
+                }
+                roc_http::ResponseToHost::Proxy(proxy) => {
+                    proxy_request(proxy, method, headers, body).await
+                }
+            }
         }
-        Err(_) => {
+        Err(BodyError::TooLarge) => payload_too_large(),
+        Err(BodyError::Io) => {
             hyper::Response::builder()
                 .status(hyper::StatusCode::BAD_REQUEST)
                 .body("Error receiving HTTP request body".into())
@@ -106,6 +159,494 @@ async fn handle_req(req: hyper::Request<hyper::Body>) -> hyper::Response<hyper::
     }
 }
 
+/// The maximum request body size the server will buffer before responding with
+/// `413 Payload Too Large`, overridable via [`MAX_BODY_BYTES_ENV_NAME`].
+fn max_body_bytes() -> usize {
+    env::var(MAX_BODY_BYTES_ENV_NAME)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+}
+
+/// Parse the `Content-Length` header, if present and well-formed.
+fn content_length(headers: &hyper::HeaderMap) -> Option<usize> {
+    headers
+        .get(hyper::header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse().ok())
+}
+
+enum BodyError {
+    /// The body exceeded the configured limit mid-stream.
+    TooLarge,
+    /// The underlying transport errored while reading the body.
+    Io,
+}
+
+/// Accumulate the body chunk-by-chunk, bailing out with [`BodyError::TooLarge`]
+/// the moment the running length crosses `limit` so an oversized upload is never
+/// fully buffered into memory.
+async fn collect_body(mut body: hyper::Body, limit: usize) -> Result<Bytes, BodyError> {
+    use hyper::body::HttpBody;
+
+    let mut collected = bytes::BytesMut::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| BodyError::Io)?;
+        if collected.len() + chunk.len() > limit {
+            return Err(BodyError::TooLarge);
+        }
+        collected.extend_from_slice(&chunk);
+    }
+
+    Ok(collected.freeze())
+}
+
+fn payload_too_large() -> hyper::Response<hyper::Body> {
+    hyper::Response::builder()
+        .status(hyper::StatusCode::PAYLOAD_TOO_LARGE)
+        .body("Request body exceeds the configured maximum size".into())
+        .unwrap() // TODO don't unwrap here
+}
+
+/// Whether the client listed `gzip` or the `*` wildcard as an accepted content
+/// coding (ignoring entries explicitly disabled with `q=0`).
+fn accepts_gzip(headers: &hyper::HeaderMap) -> bool {
+    headers
+        .get(hyper::header::ACCEPT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| {
+            value.split(',').any(|entry| {
+                let mut parts = entry.split(';').map(str::trim);
+                let coding = parts.next().unwrap_or_default();
+                let disabled = parts.any(|param| param.replace(' ', "") == "q=0");
+                (coding.eq_ignore_ascii_case("gzip") || coding == "*") && !disabled
+            })
+        })
+}
+
+/// Compression is on by default; set [`COMPRESSION_ENV_NAME`] to `0`/`false` to
+/// disable it for apps that already serve precompressed assets.
+fn compression_enabled() -> bool {
+    env::var(COMPRESSION_ENV_NAME)
+        .map(|value| value != "0" && !value.eq_ignore_ascii_case("false"))
+        .unwrap_or(true)
+}
+
+fn compression_min_bytes() -> usize {
+    env::var(COMPRESSION_MIN_BYTES_ENV_NAME)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_COMPRESSION_MIN_BYTES)
+}
+
+/// Gzip-compress the response body when the client accepts it, compression is
+/// enabled, the response isn't already encoded, and the body clears the size
+/// threshold. When the client does not accept gzip, compression is disabled or
+/// the response is already encoded, the response is returned untouched; when
+/// only the size threshold is missed (or gzip fails), the body is returned
+/// uncompressed with `Vary: accept-encoding` added.
+async fn maybe_compress(
+    response: hyper::Response<hyper::Body>,
+    accepts_gzip: bool,
+) -> hyper::Response<hyper::Body> {
+    if !accepts_gzip
+        || !compression_enabled()
+        || response
+            .headers()
+            .contains_key(hyper::header::CONTENT_ENCODING)
+    {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+
+    #[allow(deprecated)]
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            return hyper::Response::builder()
+                .status(hyper::StatusCode::INTERNAL_SERVER_ERROR)
+                .body("Error reading response body for compression".into())
+                .unwrap(); // TODO don't unwrap here
+        }
+    };
+
+    // Always advertise that the response varies on `Accept-Encoding`, even when
+    // the body is too small to be worth compressing this time.
+    parts
+        .headers
+        .append(hyper::header::VARY, HeaderValue::from_static("accept-encoding"));
+
+    if bytes.len() < compression_min_bytes() {
+        return hyper::Response::from_parts(parts, hyper::Body::from(bytes));
+    }
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    let compressed = match encoder.write_all(&bytes).and_then(|_| encoder.finish()) {
+        Ok(compressed) => compressed,
+        // Fall back to the uncompressed body if gzip somehow fails.
+        Err(_) => return hyper::Response::from_parts(parts, hyper::Body::from(bytes)),
+    };
+
+    parts.headers.insert(
+        hyper::header::CONTENT_ENCODING,
+        HeaderValue::from_static("gzip"),
+    );
+    parts.headers.insert(
+        hyper::header::CONTENT_LENGTH,
+        HeaderValue::from(compressed.len()),
+    );
+
+    hyper::Response::from_parts(parts, hyper::Body::from(compressed))
+}
+
+static HTTP_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+/// The shared client used for reverse-proxied requests, so upstream connections
+/// are pooled across requests instead of reconnecting every time.
+fn http_client() -> &'static reqwest::Client {
+    HTTP_CLIENT.get_or_init(reqwest::Client::new)
+}
+
+/// Headers that apply only to a single transport hop and must not be forwarded
+/// across a proxy boundary, per RFC 7230 section 6.1.
+const HOP_BY_HOP_HEADERS: &[&str] = &[
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailers",
+    "transfer-encoding",
+    "upgrade",
+];
+
+/// Remove the fixed hop-by-hop headers plus any headers named in a `Connection`
+/// header's value, which lists further headers that are hop-by-hop for this
+/// connection specifically.
+fn strip_hop_by_hop_headers(headers: &mut hyper::HeaderMap) {
+    let connection_tokens: Vec<String> = headers
+        .get_all(hyper::header::CONNECTION)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|token| token.trim().to_ascii_lowercase())
+        .collect();
+
+    for name in HOP_BY_HOP_HEADERS.iter().chain(connection_tokens.iter().map(String::as_str)) {
+        if let Ok(name) = HeaderName::from_bytes(name.as_bytes()) {
+            headers.remove(name);
+        }
+    }
+}
+
+/// Replace the client-facing `Host` header with the upstream authority, so a
+/// proxied request to a virtual-hosted upstream reaches the right site instead
+/// of the one the original client asked for.
+fn set_host_header(headers: &mut hyper::HeaderMap, url: &RocStr) {
+    let Ok(uri) = url.as_str().parse::<hyper::Uri>() else {
+        return;
+    };
+    let Some(authority) = uri.authority() else {
+        return;
+    };
+    let Ok(value) = HeaderValue::from_str(authority.as_str()) else {
+        return;
+    };
+
+    headers.insert(hyper::header::HOST, value);
+}
+
+/// Re-issue the original request to `directive.url` with the shared
+/// [`http_client`] and stream the upstream response straight back to the
+/// caller, turning the webserver into a programmable reverse proxy.
+async fn proxy_request(
+    directive: roc_http::ProxyToHost,
+    method: hyper::Method,
+    mut headers: hyper::HeaderMap,
+    body: Bytes,
+) -> hyper::Response<hyper::Body> {
+    strip_hop_by_hop_headers(&mut headers);
+    set_host_header(&mut headers, &directive.url);
+
+    let mut request = http_client()
+        .request(method, directive.url.as_str())
+        .headers(headers)
+        .body(body);
+
+    // Bound the whole upstream round trip (connect + read) by the timeout the
+    // Roc app set on the original request.
+    if directive.timeout_ms > 0 {
+        request = request.timeout(std::time::Duration::from_millis(directive.timeout_ms));
+    }
+
+    let upstream = match request.send().await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            eprintln!("Error proxying request upstream: {}", err); // TODO improve this
+
+            return hyper::Response::builder()
+                .status(hyper::StatusCode::BAD_GATEWAY)
+                .body("Error forwarding request to upstream".into())
+                .unwrap(); // TODO don't unwrap here
+        }
+    };
+
+    let status = upstream.status();
+    let mut response_headers = upstream.headers().clone();
+    strip_hop_by_hop_headers(&mut response_headers);
+
+    let mut response = hyper::Response::new(hyper::Body::wrap_stream(upstream.bytes_stream()));
+    *response.status_mut() = status;
+    *response.headers_mut() = response_headers;
+
+    response
+}
+
+/// Returns true when the request carries the RFC 6455 handshake headers, i.e.
+/// `Connection: Upgrade` together with `Upgrade: websocket`.
+fn is_websocket_upgrade(headers: &hyper::HeaderMap) -> bool {
+    let header_contains = |name: hyper::header::HeaderName, token: &str| {
+        headers.get(name).and_then(|value| value.to_str().ok()).is_some_and(|value| {
+            value
+                .split(',')
+                .any(|part| part.trim().eq_ignore_ascii_case(token))
+        })
+    };
+
+    header_contains(hyper::header::CONNECTION, "upgrade")
+        && header_contains(hyper::header::UPGRADE, "websocket")
+}
+
+/// Base64-encode SHA-1(`client_key` + [`WEBSOCKET_GUID`]) per RFC 6455.
+fn compute_accept_key(client_key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(client_key.as_bytes());
+    hasher.update(WEBSOCKET_GUID.as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+}
+
+/// Ask the Roc app whether it wants to accept the WebSocket upgrade. Mirrors
+/// [`call_roc`] but drives the dedicated accept entry point and carries no body,
+/// since handshakes don't have one.
+fn call_roc_websocket_open<'a>(
+    method: reqwest::Method,
+    url: hyper::Uri,
+    headers: impl Iterator<Item = (&'a HeaderName, &'a HeaderValue)>,
+) -> bool {
+    let headers: RocList<roc_http::Header> = headers
+        .map(|(key, value)| roc_http::Header {
+            name: key.as_str().into(),
+            value: value
+                .to_str()
+                .expect("valid header value from hyper")
+                .into(),
+        })
+        .collect();
+
+    let roc_request = roc_http::RequestToAndFromHost {
+        body: RocList::empty(),
+        headers,
+        method: roc_http::MethodTag::Get,
+        uri: url.to_string().as_str().into(),
+        method_ext: if method == reqwest::Method::GET {
+            RocStr::empty()
+        } else {
+            method.as_str().into()
+        },
+        timeout_ms: 0,
+    };
+
+    roc::call_roc_websocket_open(
+        roc_request,
+        ROC_MODEL.get().expect("Model was initialized at startup"),
+    )
+}
+
+/// Perform the `101 Switching Protocols` handshake and hand the upgraded stream
+/// to a duplex frame loop driven by Roc.
+async fn handle_websocket(req: hyper::Request<hyper::Body>) -> hyper::Response<hyper::Body> {
+    let (parts, body) = req.into_parts();
+    drop(body);
+
+    let accept = {
+        let headers = parts.headers.clone();
+        let method = parts.method.clone();
+        let uri = parts.uri.clone();
+        spawn_blocking(move || call_roc_websocket_open(method, uri, headers.iter()))
+            .await
+            .unwrap_or(false) // a panic in the handler declines the upgrade
+    };
+
+    let client_key = parts
+        .headers
+        .get("sec-websocket-key")
+        .and_then(|value| value.to_str().ok())
+        .map(|key| key.to_string());
+
+    let accept_key = match (accept, client_key) {
+        (true, Some(key)) => compute_accept_key(&key),
+        _ => {
+            return hyper::Response::builder()
+                .status(hyper::StatusCode::BAD_REQUEST)
+                .body("Not a WebSocket upgrade the Roc app accepted".into())
+                .unwrap(); // TODO don't unwrap here
+        }
+    };
+
+    // Reassemble the request so `hyper::upgrade::on` can hand us the stream once
+    // the `101` response has been flushed to the client.
+    let req = hyper::Request::from_parts(parts, hyper::Body::empty());
+    tokio::spawn(async move {
+        match hyper::upgrade::on(req).await {
+            Ok(upgraded) => run_websocket_loop(upgraded).await,
+            Err(err) => eprintln!("WebSocket upgrade failed: {}", err), // TODO improve this
+        }
+    });
+
+    hyper::Response::builder()
+        .status(hyper::StatusCode::SWITCHING_PROTOCOLS)
+        .header(hyper::header::CONNECTION, "upgrade")
+        .header(hyper::header::UPGRADE, "websocket")
+        .header("sec-websocket-accept", accept_key)
+        .body(hyper::Body::empty())
+        .unwrap() // TODO don't unwrap here
+}
+
+/// Minimal duplex frame loop: read one client frame at a time, hand
+/// text/binary payloads to Roc, and write back whatever frames it returns.
+/// Control frames (ping/close) are answered here without involving Roc.
+async fn run_websocket_loop(mut stream: hyper::upgrade::Upgraded) {
+    loop {
+        let frame = match read_frame(&mut stream).await {
+            Ok(Some(frame)) => frame,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("Error reading WebSocket frame: {}", err); // TODO improve this
+                break;
+            }
+        };
+
+        let (opcode, payload) = frame;
+        match opcode {
+            // Text (0x1) and Binary (0x2) frames are dispatched to Roc.
+            0x1 | 0x2 => {
+                let is_binary = opcode == 0x2;
+                let model = ROC_MODEL.get().expect("Model was initialized at startup");
+                let data = RocList::from_slice(&payload);
+                let replies = spawn_blocking(move || {
+                    roc::call_roc_websocket_message(is_binary, data, model)
+                })
+                .await
+                .unwrap_or_else(|_| RocList::empty());
+
+                for reply in replies.as_slice() {
+                    let reply_opcode = if reply.binary { 0x2 } else { 0x1 };
+                    if write_frame(&mut stream, reply_opcode, reply.data.as_slice())
+                        .await
+                        .is_err()
+                    {
+                        return;
+                    }
+                }
+            }
+            // Ping (0x9) -> Pong (0xA) with the same payload.
+            0x9 => {
+                if write_frame(&mut stream, 0xA, &payload).await.is_err() {
+                    return;
+                }
+            }
+            // Close (0x8): echo the close frame and stop.
+            0x8 => {
+                let _ = write_frame(&mut stream, 0x8, &payload).await;
+                break;
+            }
+            // Pong (0xA) and any unknown opcodes are ignored.
+            _ => {}
+        }
+    }
+}
+
+/// Read a single WebSocket frame, returning its opcode and unmasked payload, or
+/// `None` at end of stream. Fragmentation is not reassembled. Per RFC 6455
+/// §5.1 every client frame must be masked, and the advertised length is capped
+/// by [`max_body_bytes`] so a single frame header can't drive an unbounded
+/// allocation the way an unchecked `Content-Length` could before chunk0-3.
+async fn read_frame(stream: &mut hyper::upgrade::Upgraded) -> io::Result<Option<(u8, Vec<u8>)>> {
+    let mut header = [0u8; 2];
+    match stream.read_exact(&mut header).await {
+        Ok(_) => {}
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(err),
+    }
+
+    let opcode = header[0] & 0x0f;
+    let masked = header[1] & 0x80 != 0;
+    let mut len = (header[1] & 0x7f) as u64;
+
+    if len == 126 {
+        let mut ext = [0u8; 2];
+        stream.read_exact(&mut ext).await?;
+        len = u16::from_be_bytes(ext) as u64;
+    } else if len == 127 {
+        let mut ext = [0u8; 8];
+        stream.read_exact(&mut ext).await?;
+        len = u64::from_be_bytes(ext);
+    }
+
+    if !masked {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "client WebSocket frame was not masked",
+        ));
+    }
+
+    if len > max_body_bytes() as u64 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "WebSocket frame payload exceeds the configured maximum size",
+        ));
+    }
+    let len = len as usize;
+
+    let mut mask = [0u8; 4];
+    stream.read_exact(&mut mask).await?;
+
+    let mut payload = vec![0u8; len];
+    stream.read_exact(&mut payload).await?;
+
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+
+    Ok(Some((opcode, payload)))
+}
+
+/// Write a single unfragmented, unmasked server frame with the given opcode.
+async fn write_frame(
+    stream: &mut hyper::upgrade::Upgraded,
+    opcode: u8,
+    payload: &[u8],
+) -> io::Result<()> {
+    let mut frame = Vec::with_capacity(payload.len() + 10);
+    frame.push(0x80 | opcode); // FIN bit set, single frame
+
+    let len = payload.len();
+    if len < 126 {
+        frame.push(len as u8);
+    } else if len <= u16::MAX as usize {
+        frame.push(126);
+        frame.extend_from_slice(&(len as u16).to_be_bytes());
+    } else {
+        frame.push(127);
+        frame.extend_from_slice(&(len as u64).to_be_bytes());
+    }
+
+    frame.extend_from_slice(payload);
+    stream.write_all(&frame).await?;
+    stream.flush().await
+}
+
 /// Translate Rust panics in the given Future into 500 errors
 async fn handle_panics(
     fut: impl Future<Output = hyper::Response<hyper::Body>>,
@@ -123,26 +664,258 @@ async fn handle_panics(
     }
 }
 
+/// Load the PEM certificate chain and private key referenced by the TLS env
+/// vars and build a `rustls::ServerConfig`. Returns `None` (and the server
+/// falls back to plaintext HTTP) unless both the cert and the key env vars are
+/// set.
+fn load_tls_config() -> Option<io::Result<rustls::ServerConfig>> {
+    let cert_path = env::var(TLS_CERT_ENV_NAME).ok()?;
+    let key_path = env::var(TLS_KEY_ENV_NAME).ok()?;
+
+    Some(build_tls_config(&cert_path, &key_path))
+}
+
+/// Read the first private key out of `key_path`, accepting PKCS#8
+/// (`BEGIN PRIVATE KEY`), PKCS#1 (`BEGIN RSA PRIVATE KEY`) and SEC1
+/// (`BEGIN EC PRIVATE KEY`) PEM encodings.
+fn read_private_key(key_path: &str) -> io::Result<Vec<u8>> {
+    let mut reader = BufReader::new(File::open(key_path)?);
+
+    loop {
+        match rustls_pemfile::read_one(&mut reader)? {
+            Some(rustls_pemfile::Item::PKCS8Key(key)) => return Ok(key),
+            Some(rustls_pemfile::Item::RSAKey(key)) => return Ok(key),
+            Some(rustls_pemfile::Item::ECKey(key)) => return Ok(key),
+            Some(_) => continue,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "no private key (PKCS#8, PKCS#1 or SEC1) in TLS key file",
+                ))
+            }
+        }
+    }
+}
+
+fn build_tls_config(cert_path: &str, key_path: &str) -> io::Result<rustls::ServerConfig> {
+    let mut cert_reader = BufReader::new(File::open(cert_path)?);
+    // The leaf certificate plus any intermediates are expected to already be
+    // concatenated in this PEM file; unrelated root CAs from the native trust
+    // store have no place in the chain the server presents to clients.
+    let certs: Vec<rustls::Certificate> = rustls_pemfile::certs(&mut cert_reader)?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+
+    let key = read_private_key(key_path)?;
+
+    let mut config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_no_client_auth()
+        .with_single_cert(certs, rustls::PrivateKey(key))
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    // ALPN defaults to offering HTTP/1.1; apps can opt into HTTP/2 (or restrict
+    // the set) with a comma-separated list in the ALPN env var.
+    config.alpn_protocols = env::var(TLS_ALPN_ENV_NAME)
+        .ok()
+        .map(|protos| {
+            protos
+                .split(',')
+                .map(|proto| proto.trim().as_bytes().to_vec())
+                .filter(|proto| !proto.is_empty())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_else(|| vec![b"http/1.1".to_vec()]);
+
+    Ok(config)
+}
+
+/// Resolves once either SIGINT (Ctrl+C) or, on Unix, SIGTERM is received, so
+/// both servers can stop accepting new connections and drain what's in flight.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Counts in-flight TLS connections so [`run_tls_server`] can wait for them to
+/// finish after the accept loop stops, rather than dropping them mid-request.
+/// (The plaintext path gets this for free from `hyper::Server::with_graceful_shutdown`.)
+struct ConnectionTracker {
+    count: AtomicUsize,
+    drained: Notify,
+}
+
+impl ConnectionTracker {
+    fn new() -> Self {
+        Self {
+            count: AtomicUsize::new(0),
+            drained: Notify::new(),
+        }
+    }
+
+    fn track(self: &Arc<Self>) -> ConnectionGuard {
+        self.count.fetch_add(1, Ordering::SeqCst);
+
+        ConnectionGuard {
+            tracker: self.clone(),
+        }
+    }
+
+    async fn wait_for_drain(&self) {
+        loop {
+            // `enable()` registers this waiter with `Notify` immediately, so a
+            // `notify_waiters()` call that races with the count check below
+            // (after `enable` but before `await`) still wakes us, instead of
+            // being lost the way an un-enabled `Notified` future would miss it.
+            let notified = self.drained.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.count.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+
+            notified.await;
+        }
+    }
+}
+
+struct ConnectionGuard {
+    tracker: Arc<ConnectionTracker>,
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        if self.tracker.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.tracker.drained.notify_waiters();
+        }
+    }
+}
+
+fn service() -> impl hyper::service::Service<
+    hyper::Request<hyper::Body>,
+    Response = hyper::Response<hyper::Body>,
+    Error = Infallible,
+    Future = impl Future<Output = Result<hyper::Response<hyper::Body>, Infallible>>,
+> {
+    hyper::service::service_fn(|req| handle_panics(handle_req(req)))
+}
+
 async fn run_server() -> i32 {
     let host = env::var(HOST_ENV_NAME).unwrap_or("127.0.0.1".to_string());
     let port = env::var(PORT_ENV_NAME).unwrap_or(DEFAULT_PORT.to_string());
     let addr = format!("{}:{}", host, port)
         .parse::<SocketAddr>()
         .expect("Failed to parse host and port");
-    let server = hyper::Server::bind(&addr).serve(hyper::service::make_service_fn(|_conn| async {
-        Ok::<_, Infallible>(hyper::service::service_fn(|req| {
-            handle_panics(handle_req(req))
-        }))
-    }));
 
-    println!("Listening on <http://{host}:{port}>");
+    match load_tls_config() {
+        Some(Ok(tls_config)) => {
+            println!("Listening on <https://{host}:{port}>");
+            run_tls_server(addr, tls_config).await
+        }
+        Some(Err(err)) => {
+            eprintln!("Error loading TLS certificate/key: {}", err); // TODO improve this
+
+            1
+        }
+        None => {
+            let server = hyper::Server::bind(&addr)
+                .serve(hyper::service::make_service_fn(|_conn| async {
+                    Ok::<_, Infallible>(service())
+                }))
+                .with_graceful_shutdown(shutdown_signal());
+
+            println!("Listening on <http://{host}:{port}>");
 
-    match server.await {
-        Ok(_) => 0,
+            match server.await {
+                Ok(_) => 0,
+                Err(err) => {
+                    eprintln!("Error initializing Rust `hyper` server: {}", err); // TODO improve this
+
+                    1
+                }
+            }
+        }
+    }
+}
+
+/// Accept loop for the TLS listener. `hyper::Server::bind` expects an `Accept`,
+/// so we drive the accept/handshake manually and hand each upgraded TLS stream
+/// to `serve_connection` on its own task. Stops accepting on a shutdown signal
+/// and waits for in-flight connections to finish before returning.
+async fn run_tls_server(addr: SocketAddr, tls_config: rustls::ServerConfig) -> i32 {
+    let acceptor = TlsAcceptor::from(Arc::new(tls_config));
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
         Err(err) => {
-            eprintln!("Error initializing Rust `hyper` server: {}", err); // TODO improve this
+            eprintln!("Error binding TLS listener: {}", err); // TODO improve this
 
-            1
+            return 1;
         }
+    };
+
+    let tracker = Arc::new(ConnectionTracker::new());
+    let mut shutdown = Box::pin(shutdown_signal());
+
+    loop {
+        let (tcp_stream, _peer) = tokio::select! {
+            conn = listener.accept() => match conn {
+                Ok(conn) => conn,
+                Err(err) => {
+                    eprintln!("Error accepting TCP connection: {}", err); // TODO improve this
+
+                    continue;
+                }
+            },
+            _ = &mut shutdown => {
+                println!("Shutdown signal received, draining in-flight TLS connections...");
+                break;
+            }
+        };
+
+        let acceptor = acceptor.clone();
+        let guard = tracker.track();
+        tokio::spawn(async move {
+            let _guard = guard;
+
+            match acceptor.accept(tcp_stream).await {
+                Ok(tls_stream) => {
+                    if let Err(err) = hyper::server::conn::Http::new()
+                        .serve_connection(tls_stream, service())
+                        .with_upgrades()
+                        .await
+                    {
+                        eprintln!("Error serving TLS connection: {}", err); // TODO improve this
+                    }
+                }
+                Err(err) => {
+                    eprintln!("TLS handshake failed: {}", err); // TODO improve this
+                }
+            }
+        });
     }
+
+    tracker.wait_for_drain().await;
+
+    0
 }